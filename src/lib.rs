@@ -25,18 +25,6 @@ pub trait IndexCallable {
                   -> Self::Item;
 }
 
-/// Tag for unsigned integer type
-pub trait UnsignedIndexable {
-    /// Convert from ItemNum index
-    fn from_index(self) -> usize;
-}
-
-impl UnsignedIndexable for c_uint {
-    fn from_index(self) -> usize {
-        self as usize
-    }
-}
-
 /// Tag for signed integer type
 pub trait SignedIndexable {
     /// Convert from ItemNum index
@@ -49,39 +37,96 @@ impl SignedIndexable for c_int {
     }
 }
 
-/// Convert usize index to ItemNum
-pub trait Indexable {
-    /// Convert to ItemNum index
-    fn as_index(idx: usize) -> Self;
+/// A typed index/count, modeled on rustc's own index newtype abstraction.
+pub trait Idx: Copy + Eq {
+    /// Build an index from a plain `usize`, e.g. a loop counter.
+    fn new(idx: usize) -> Self;
+
+    /// Recover the plain `usize` value of this index.
+    fn index(self) -> usize;
 }
 
-impl Indexable for c_uint {
-    fn as_index(idx: usize) -> c_uint {
+/// Marker for an `Idx` that is always non-negative, so `fetch_item_num`
+/// can be trusted directly without the `new_check_positive` sentinel check.
+pub trait UnsignedIdx: Idx {}
+
+// `c_uint` is a type alias for `u32` (and `c_int` for `i32`) on every
+// platform Rust supports, so a single impl covers both the FFI alias and
+// the plain type; there is no separate `impl Idx for u32` to write.
+impl Idx for c_uint {
+    fn new(idx: usize) -> c_uint {
+        assert!(idx <= c_uint::max_value() as usize);
         idx as c_uint
     }
+    fn index(self) -> usize {
+        self as usize
+    }
 }
 
-impl Indexable for c_int {
-    fn as_index(idx: usize) -> c_int {
+impl UnsignedIdx for c_uint {}
+
+impl Idx for c_int {
+    fn new(idx: usize) -> c_int {
+        assert!(idx <= c_int::max_value() as usize);
         idx as c_int
     }
+    fn index(self) -> usize {
+        assert!(self >= 0);
+        self as usize
+    }
+}
+
+impl Idx for usize {
+    fn new(idx: usize) -> usize {
+        idx
+    }
+    fn index(self) -> usize {
+        self
+    }
+}
+
+impl UnsignedIdx for usize {}
+
+/// Implement `Idx` and `UnsignedIdx` for a tuple-struct newtype wrapping an
+/// FFI count, e.g. `impl_idx_newtype!(NodeIdx, u32);`. `$inner` must itself
+/// be an `UnsignedIdx`; a signed inner type such as `c_int` does not compile.
+#[macro_export]
+macro_rules! impl_idx_newtype {
+    ($name:ident, $inner:ty) => {
+        impl $crate::Idx for $name {
+            fn new(idx: usize) -> Self {
+                $name(<$inner as $crate::Idx>::new(idx))
+            }
+            fn index(self) -> usize {
+                $crate::Idx::index(self.0)
+            }
+        }
+        impl $crate::UnsignedIdx for $name where $inner: $crate::UnsignedIdx {}
+    };
 }
 
 
 /// An iterator for a type's template arguments
 pub struct IndexCallIterator<CxtT> {
     cxt: CxtT,
+    /// Total number of items, as reported by `fetch_item_num`. Kept around so
+    /// `fetch_item` always receives the original count, even once `length`
+    /// has been pulled in from the back by `next_back`.
+    original_len: usize,
+    /// Exclusive back cursor: items in `index..length` remain to be yielded.
     length: usize,
+    /// Front cursor.
     index: usize,
 }
 
 impl<CxtT: IndexCallable> IndexCallIterator<CxtT>
-    where CxtT::ItemNum: UnsignedIndexable,
+    where CxtT::ItemNum: UnsignedIdx,
 {
     fn new(cxt: CxtT) -> IndexCallIterator<CxtT> {
-        let len: usize = cxt.fetch_item_num().from_index();
+        let len: usize = cxt.fetch_item_num().index();
         IndexCallIterator {
             cxt: cxt,
+            original_len: len,
             length: len,
             index: 0,
         }
@@ -89,11 +134,11 @@ impl<CxtT: IndexCallable> IndexCallIterator<CxtT>
 }
 
 pub fn new_index_call_iterator<FLen, F, T>
-    (f_len: FLen,
+    (mut f_len: FLen,
      f: F)
      -> Box<ExactSizeIterator<Item = T>>
-    where F: Fn(c_uint) -> T + 'static,
-          FLen: Fn() -> c_uint,
+    where F: FnMut(c_uint) -> T + 'static,
+          FLen: FnMut() -> c_uint,
 {
     Box::new((0..f_len()).map(f))
 }
@@ -106,6 +151,7 @@ impl<CxtT: IndexCallable> IndexCallIterator<CxtT>
         if len >= 0 {
             Some(IndexCallIterator {
                 cxt: cxt,
+                original_len: len as usize,
                 length: len as usize,
                 index: 0,
             })
@@ -117,11 +163,11 @@ impl<CxtT: IndexCallable> IndexCallIterator<CxtT>
 }
 
 pub fn new_index_call_iterator_check_positive<FLen, F, T>
-    (f_len: FLen,
+    (mut f_len: FLen,
      f: F)
      -> Option<Box<ExactSizeIterator<Item = T>>>
-    where F: Fn(c_int) -> T + 'static,
-          FLen: Fn() -> c_int,
+    where F: FnMut(c_int) -> T + 'static,
+          FLen: FnMut() -> c_int,
 {
     let len = f_len();
     if len >= 0 {
@@ -133,23 +179,34 @@ pub fn new_index_call_iterator_check_positive<FLen, F, T>
 }
 
 impl<CxtT: IndexCallable> Iterator for IndexCallIterator<CxtT>
-    where CxtT::ItemNum: Indexable,
+    where CxtT::ItemNum: Idx,
 {
     type Item = CxtT::Item;
     fn next(&mut self) -> Option<Self::Item> {
         if self.index < self.length {
             let idx = self.index;
             self.index += 1;
-            Some(self.cxt.fetch_item(CxtT::ItemNum::as_index(idx),
-                                     CxtT::ItemNum::as_index(self.length)))
+            Some(self.cxt.fetch_item(CxtT::ItemNum::new(idx),
+                                     CxtT::ItemNum::new(self.original_len)))
         } else {
             None
         }
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.index = self.index.saturating_add(n).min(self.length);
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        assert!(self.index <= self.length);
+        let len = self.length - self.index;
+        (len, Some(len))
+    }
 }
 
 impl<CxtT: IndexCallable> ExactSizeIterator for IndexCallIterator<CxtT>
-    where CxtT::ItemNum: Indexable,
+    where CxtT::ItemNum: Idx,
 {
     fn len(&self) -> usize {
         assert!(self.index <= self.length);
@@ -157,17 +214,31 @@ impl<CxtT: IndexCallable> ExactSizeIterator for IndexCallIterator<CxtT>
     }
 }
 
+impl<CxtT: IndexCallable> DoubleEndedIterator for IndexCallIterator<CxtT>
+    where CxtT::ItemNum: Idx,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index < self.length {
+            self.length -= 1;
+            Some(self.cxt.fetch_item(CxtT::ItemNum::new(self.length),
+                                     CxtT::ItemNum::new(self.original_len)))
+        } else {
+            None
+        }
+    }
+}
+
 // fn map<B, F>(self, f: F) -> Map<Self, F> where
 //     Self: Sized, F: FnMut(Self::Item) -> B,
 // {
 //     Map{iter: self, f: f}
 // }
 
-pub fn new_index_call_iterator_u32<FLen, F>(f_len: FLen,
-                                            f: F)
+pub fn new_index_call_iterator_u32<FLen, F>(mut f_len: FLen,
+                                            mut f: F)
                                             -> Box<Iterator<Item = u32>>
-    where F: Fn(c_uint) -> c_uint + 'static,
-          FLen: Fn() -> c_uint,
+    where F: FnMut(c_uint) -> c_uint + 'static,
+          FLen: FnMut() -> c_uint,
 {
     Box::new((0..f_len()).map(move |x| f(x)))
 }
@@ -187,6 +258,149 @@ pub fn new_index_call_iter_boxed<F>(len: c_uint,
 }
 
 
+/// Provide index results, but fallibly.
+pub trait TryIndexCallable {
+    /// Item type for Iterator trait.
+    type Item;
+
+    /// Error type shared by both fallible calls.
+    type Error;
+
+    /// Call the function retreiving number of items, mapping any
+    /// negative/sentinel code to an `Err`.
+    fn fetch_item_num(&self) -> Result<usize, Self::Error>;
+
+    /// Call the function retreiving the item for the index idx.
+    /// This will always be called with 0 <= idx < num.
+    /// num will always be the value returned by fetch_item_num.
+    fn fetch_item(&mut self, idx: usize, num: usize) -> Result<Self::Item, Self::Error>;
+}
+
+/// An iterator that short-circuits to `None` after its first `Err`.
+pub struct TryIndexCallIterator<CxtT: TryIndexCallable> {
+    cxt: CxtT,
+    length: usize,
+    index: usize,
+    errored: bool,
+}
+
+impl<CxtT: TryIndexCallable> TryIndexCallIterator<CxtT> {
+    fn new(cxt: CxtT) -> Result<TryIndexCallIterator<CxtT>, CxtT::Error> {
+        let len = cxt.fetch_item_num()?;
+        Ok(TryIndexCallIterator {
+            cxt: cxt,
+            length: len,
+            index: 0,
+            errored: false,
+        })
+    }
+}
+
+impl<CxtT: TryIndexCallable> Iterator for TryIndexCallIterator<CxtT> {
+    type Item = Result<CxtT::Item, CxtT::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.errored || self.index >= self.length {
+            return None;
+        }
+        let idx = self.index;
+        self.index += 1;
+        match self.cxt.fetch_item(idx, self.length) {
+            Ok(item) => Some(Ok(item)),
+            Err(e) => {
+                self.errored = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Adapts a pair of FFI closures into a `TryIndexCallable`.
+struct ClosureTryIndexCallable<FLen, F> {
+    f_len: FLen,
+    f: F,
+}
+
+impl<FLen, F, T, E> TryIndexCallable for ClosureTryIndexCallable<FLen, F>
+    where FLen: Fn() -> Result<usize, E>,
+          F: FnMut(usize) -> Result<T, E>,
+{
+    type Item = T;
+    type Error = E;
+
+    fn fetch_item_num(&self) -> Result<usize, E> {
+        (self.f_len)()
+    }
+
+    fn fetch_item(&mut self, idx: usize, _num: usize) -> Result<T, E> {
+        (self.f)(idx)
+    }
+}
+
+pub fn try_index_call_iterator<FLen, F, T, E>
+    (f_len: FLen,
+     f: F)
+     -> Box<Iterator<Item = Result<T, E>>>
+    where FLen: Fn() -> Result<usize, E> + 'static,
+          F: FnMut(usize) -> Result<T, E> + 'static,
+          T: 'static,
+          E: 'static,
+{
+    let cxt = ClosureTryIndexCallable { f_len: f_len, f: f };
+    match TryIndexCallIterator::new(cxt) {
+        Ok(iter) => Box::new(iter),
+        Err(e) => Box::new(Some(Err(e)).into_iter()),
+    }
+}
+
+
+/// An iterator for `new_index_call_iter_step`, mirroring `std::iter::range_step`.
+pub struct IndexCallIterStep<F> {
+    f: F,
+    index: usize,
+    length: usize,
+    step: usize,
+}
+
+pub fn new_index_call_iter_step<F>(len: c_uint, step: usize, f: F) -> IndexCallIterStep<F>
+    where F: Fn(c_uint) -> c_uint,
+{
+    assert!(step != 0, "step must not be 0");
+    IndexCallIterStep {
+        f: f,
+        index: 0,
+        length: len as usize,
+        step: step,
+    }
+}
+
+impl<F> Iterator for IndexCallIterStep<F>
+    where F: Fn(c_uint) -> c_uint,
+{
+    type Item = c_uint;
+    fn next(&mut self) -> Option<c_uint> {
+        if self.index >= self.length {
+            return None;
+        }
+        let idx = self.index;
+        self.index += self.step;
+        Some((self.f)(idx as c_uint))
+    }
+}
+
+impl<F> ExactSizeIterator for IndexCallIterStep<F>
+    where F: Fn(c_uint) -> c_uint,
+{
+    fn len(&self) -> usize {
+        if self.index >= self.length {
+            0
+        } else {
+            (self.length - self.index).div_ceil(self.step)
+        }
+    }
+}
+
+
 // fn(A) -> (A, A)
 pub fn new_ret_closure() -> Box<Fn(u32) -> u32> {
     Box::new(move |x| x + 2)
@@ -294,6 +508,31 @@ mod tests {
         assert_eq!(len, 2);
     }
 
+    #[test]
+    fn test_index_call_iterator_rev() {
+        let provider = TestIndexCallableProvider {
+            cxti: 3,
+            cxtu: 5,
+        };
+
+        let values = provider.get_unsigned_children();
+        assert_eq!(values.rev().collect::<Vec<_>>(), vec![4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_index_call_iterator_nth_and_size_hint() {
+        let provider = TestIndexCallableProvider {
+            cxti: 3,
+            cxtu: 5,
+        };
+
+        let mut values = provider.get_unsigned_children();
+        assert_eq!(values.size_hint(), (5, Some(5)));
+        assert_eq!(values.nth(2), Some(2));
+        assert_eq!(values.size_hint(), (2, Some(2)));
+        assert_eq!(values.nth(10), None);
+    }
+
     #[test]
     fn test_optional_index_call_iterator() {
         let provider = TestIndexCallableProvider {
@@ -388,6 +627,19 @@ mod tests {
                    vec![10, 11, 12]);
     }
 
+    #[test]
+    fn test_new_index_call_iterator_with_stateful_callback() {
+        let calls = ::std::rc::Rc::new(::std::cell::Cell::new(0));
+        let calls_in_closure = calls.clone();
+        let collected = new_index_call_iterator(|| 3 as u32, move |x| {
+                             calls_in_closure.set(calls_in_closure.get() + 1);
+                             x
+                         })
+            .collect::<Vec<_>>();
+        assert_eq!(collected, vec![0, 1, 2]);
+        assert_eq!(calls.get(), 3);
+    }
+
     #[test]
     fn test_new_index_call_iterator_u32() {
         assert_eq!(new_index_call_iterator_u32(|| 0, |x| x)
@@ -414,6 +666,49 @@ mod tests {
                    vec![0, 1, 2]);
     }
 
+    #[test]
+    fn test_try_index_call_iterator_ok() {
+        let collected = try_index_call_iterator(|| Ok(3), |x| Ok(x) as Result<usize, ()>)
+            .collect::<Result<Vec<_>, _>>();
+        assert_eq!(collected, Ok(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn test_try_index_call_iterator_bad_length() {
+        let mut values = try_index_call_iterator(|| Err("bad length"), |x| Ok(x));
+        assert_eq!(values.next(), Some(Err("bad length")));
+        assert_eq!(values.next(), None);
+    }
+
+    #[test]
+    fn test_try_index_call_iterator_short_circuits_on_item_error() {
+        let mut values = try_index_call_iterator(|| Ok(3),
+                                                 |x| if x == 1 { Err("bad item") } else { Ok(x) });
+        assert_eq!(values.next(), Some(Ok(0)));
+        assert_eq!(values.next(), Some(Err("bad item")));
+        assert_eq!(values.next(), None);
+    }
+
+    #[test]
+    fn test_new_index_call_iter_step() {
+        let values = new_index_call_iter_step(10, 3, |x| x);
+        assert_eq!(values.len(), 4);
+        assert_eq!(values.collect::<Vec<_>>(), vec![0, 3, 6, 9]);
+    }
+
+    #[test]
+    fn test_new_index_call_iter_step_len_shrinks_as_consumed() {
+        let mut values = new_index_call_iter_step(10, 3, |x| x);
+        values.next();
+        assert_eq!(values.len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "step must not be 0")]
+    fn test_new_index_call_iter_step_rejects_zero_step() {
+        new_index_call_iter_step(10, 0, |x| x);
+    }
+
     // #[test]
     // fn test_new_index_call_iter_with_closuer_boxed() {
     //     let data = vec![1, 2, 3];
@@ -445,4 +740,48 @@ mod tests {
         assert_eq!(cipher_iter_with_data_and_key().collect::<Vec<_>>(),
                    vec![10, 11, 8, 9, 14, 15]);
     }
+
+    // Distinct FFI index domains, kept apart by `Idx` newtypes.
+    //
+
+    #[derive(Copy, Clone, PartialEq, Eq)]
+    struct NodeIdx(u32);
+    impl_idx_newtype!(NodeIdx, u32);
+
+    #[derive(Copy, Clone, PartialEq, Eq)]
+    struct EdgeIdx(u32);
+    impl_idx_newtype!(EdgeIdx, u32);
+
+    #[test]
+    fn test_idx_newtype_round_trip() {
+        assert_eq!(NodeIdx::new(3).index(), 3);
+        assert_eq!(EdgeIdx::new(7).index(), 7);
+    }
+
+    struct TestNodeCallable {
+        cxt: u32, // FFI function context
+    }
+
+    impl IndexCallable for TestNodeCallable {
+        type Item = NodeIdx;
+        type ItemNum = NodeIdx;
+
+        fn fetch_item_num(&self) -> Self::ItemNum {
+            NodeIdx::new(self.cxt as usize) // call specific FFI function
+        }
+
+        fn fetch_item(&mut self, idx: Self::ItemNum, num: Self::ItemNum) -> Self::Item {
+            assert!(idx.index() < num.index());
+            idx // call specific FFI function
+        }
+    }
+
+    #[test]
+    fn test_index_call_iterator_with_newtype_idx() {
+        let idx_callable = TestNodeCallable { cxt: 3 };
+        let values = IndexCallIterator::new(idx_callable);
+
+        assert_eq!(values.map(|n| n.index()).collect::<Vec<_>>(),
+                   vec![0, 1, 2]);
+    }
 }